@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use colored::*;
+use serde::Deserialize;
+
+use crate::benchmark_result::BenchmarkResult;
+use crate::units::format_duration;
+
+/// A single command's results as previously written by `--export-json`,
+/// used as the "before" side of a `--baseline` comparison.
+#[derive(Debug, Deserialize)]
+pub struct BaselineResult {
+    pub command: String,
+    pub mean: f64,
+    pub times: Vec<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BaselineFile {
+    results: Vec<BaselineResult>,
+}
+
+/// Load the baseline results from a previously exported JSON results file.
+pub fn load_baseline(path: &Path) -> Result<Vec<BaselineResult>, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("could not read baseline file '{}': {}", path.display(), e))?;
+
+    serde_json::from_str::<BaselineFile>(&content)
+        .map(|parsed| parsed.results)
+        .map_err(|e| format!("could not parse baseline file '{}': {}", path.display(), e))
+}
+
+/// The outcome of comparing one command's current run against its baseline
+/// counterpart, via Welch's unequal-variance t-test.
+pub struct BaselineComparison {
+    pub command: String,
+    pub mean_seconds: f64,
+    pub baseline_mean_seconds: f64,
+    pub percent_change: f64,
+    pub t_statistic: f64,
+    pub degrees_of_freedom: f64,
+    pub regressed: bool,
+    pub improved: bool,
+}
+
+/// Compare `results` against `baseline`, matching commands by name.
+/// Commands that have no baseline counterpart are skipped. `threshold` is
+/// the `|t|` value above which a change is considered significant (the
+/// default, 1.96, corresponds to roughly p<0.05 for reasonably large sample
+/// sizes).
+pub fn compare_against_baseline(
+    results: &[BenchmarkResult],
+    baseline: &[BaselineResult],
+    threshold: f64,
+) -> Vec<BaselineComparison> {
+    let baseline_by_command: HashMap<&str, &BaselineResult> =
+        baseline.iter().map(|b| (b.command.as_str(), b)).collect();
+
+    results
+        .iter()
+        .filter_map(|result| {
+            let base = baseline_by_command.get(result.command.as_str())?;
+            Some(compare_one(result, base, threshold))
+        })
+        .collect()
+}
+
+fn compare_one(
+    result: &BenchmarkResult,
+    base: &BaselineResult,
+    threshold: f64,
+) -> BaselineComparison {
+    let percent_change = (result.mean - base.mean) / base.mean * 100.0;
+    let n1 = base.times.len();
+    let n2 = result.times.len();
+
+    // Welch's t-test needs at least two samples per side to estimate a
+    // variance; below that, fall back to a pure ratio comparison.
+    let (t_statistic, degrees_of_freedom, significant) = if n1 < 2 || n2 < 2 {
+        (0.0, 0.0, percent_change.abs() > 1.0)
+    } else {
+        let s1_sq = sample_variance(&base.times, base.mean);
+        let s2_sq = sample_variance(&result.times, result.mean);
+
+        if s1_sq == 0.0 && s2_sq == 0.0 {
+            (0.0, 0.0, percent_change.abs() > 1.0)
+        } else {
+            let se1 = s1_sq / n1 as f64;
+            let se2 = s2_sq / n2 as f64;
+            let t = (result.mean - base.mean) / (se1 + se2).sqrt();
+            let df = (se1 + se2).powi(2)
+                / (se1.powi(2) / (n1 as f64 - 1.0) + se2.powi(2) / (n2 as f64 - 1.0));
+
+            (t, df, t.abs() > threshold)
+        }
+    };
+
+    BaselineComparison {
+        command: result.command.clone(),
+        mean_seconds: result.mean,
+        baseline_mean_seconds: base.mean,
+        percent_change,
+        t_statistic,
+        degrees_of_freedom,
+        regressed: significant && percent_change > 0.0,
+        improved: significant && percent_change < 0.0,
+    }
+}
+
+fn sample_variance(values: &[f64], mean: f64) -> f64 {
+    let n = values.len() as f64;
+    values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0)
+}
+
+/// Print a one-line summary for each baseline comparison: red for a
+/// regression, green for an improvement, plain for a change within noise.
+/// Each line also reports the absolute mean times being compared and, when a
+/// t-test (rather than the small-sample ratio fallback) decided the
+/// verdict, the `t` statistic and Welch–Satterthwaite degrees of freedom
+/// behind it.
+pub fn print_comparisons(comparisons: &[BaselineComparison]) {
+    for comparison in comparisons {
+        let times = format!(
+            "{} vs. baseline {}",
+            format_duration(comparison.mean_seconds),
+            format_duration(comparison.baseline_mean_seconds)
+        );
+        let t_test = if comparison.degrees_of_freedom > 0.0 {
+            format!(
+                ", t = {:.2}, df = {:.1}",
+                comparison.t_statistic, comparison.degrees_of_freedom
+            )
+        } else {
+            String::new()
+        };
+
+        if comparison.regressed {
+            println!(
+                "{} '{}' is {} slower than the baseline [{}{}]",
+                "Regression:".red().bold(),
+                comparison.command,
+                format!("{:+.1}%", comparison.percent_change).red(),
+                times,
+                t_test
+            );
+        } else if comparison.improved {
+            println!(
+                "{} '{}' is {} faster than the baseline [{}{}]",
+                "Improvement:".green().bold(),
+                comparison.command,
+                format!("{:+.1}%", comparison.percent_change).green(),
+                times,
+                t_test
+            );
+        } else {
+            println!(
+                "  '{}' is within noise of the baseline ({:+.1}%) [{}{}]",
+                comparison.command, comparison.percent_change, times, t_test
+            );
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use std::cmp;
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::error::OptionsError;
+use crate::options::HyperfineOptions;
+
+/// The subset of [`HyperfineOptions`] that can be supplied through a TOML
+/// configuration file. Every field is optional: a value that is absent from
+/// the file simply leaves the corresponding option untouched, so that the
+/// usual defaults (or a later CLI flag) can still apply.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ConfigFile {
+    pub warmup: Option<u64>,
+    pub min_runs: Option<u64>,
+    pub max_runs: Option<u64>,
+    pub runs: Option<u64>,
+    pub shell: Option<String>,
+    pub style: Option<String>,
+    pub time_unit: Option<String>,
+    pub prepare: Option<Vec<String>>,
+    pub setup: Option<String>,
+    pub cleanup: Option<String>,
+    pub export_json: Option<String>,
+    pub export_csv: Option<String>,
+    pub export_markdown: Option<String>,
+    pub export_asciidoc: Option<String>,
+}
+
+impl ConfigFile {
+    /// Load and parse a config file from `path`.
+    ///
+    /// Returns a plain, user-facing message (rather than a generic IO error)
+    /// when the file cannot be found, so callers can hand it straight to
+    /// [`crate::error`].
+    pub fn from_file(path: &Path) -> Result<ConfigFile, String> {
+        if !path.exists() {
+            return Err(format!("configuration file not found: {}", path.display()));
+        }
+
+        let content = fs::read_to_string(path).map_err(|e| {
+            format!(
+                "could not read configuration file '{}': {}",
+                path.display(),
+                e
+            )
+        })?;
+
+        toml::from_str(&content).map_err(|e| {
+            format!(
+                "could not parse configuration file '{}': {}",
+                path.display(),
+                e
+            )
+        })
+    }
+
+    /// Apply the warmup count, run counts and setup/prepare/cleanup commands
+    /// from this config file to `options`, as defaults that any later
+    /// CLI-derived assignment is free to override. The `shell`, `style`,
+    /// `time-unit` and export values are not applied here; callers read them
+    /// from the fields directly.
+    pub fn apply_to(&self, options: &mut HyperfineOptions) -> Result<(), OptionsError<'static>> {
+        if let Some(warmup) = self.warmup {
+            options.warmup_count = warmup;
+        }
+
+        if let Some(runs) = self.runs {
+            options.runs.min = runs;
+            options.runs.max = Some(runs);
+        } else {
+            match (self.min_runs, self.max_runs) {
+                (Some(min), None) => {
+                    options.runs.min = min;
+                }
+                (None, Some(max)) => {
+                    // Since the minimum was not explicit we lower it if max is below the default min.
+                    options.runs.min = cmp::min(options.runs.min, max);
+                    options.runs.max = Some(max);
+                }
+                (Some(min), Some(max)) if min > max => {
+                    return Err(OptionsError::EmptyRunsRange);
+                }
+                (Some(min), Some(max)) => {
+                    options.runs.min = min;
+                    options.runs.max = Some(max);
+                }
+                (None, None) => {}
+            }
+        }
+
+        if self.setup.is_some() {
+            options.setup_command = self.setup.clone();
+        }
+
+        if self.prepare.is_some() {
+            options.preparation_command = self.prepare.clone();
+        }
+
+        if self.cleanup.is_some() {
+            options.cleanup_command = self.cleanup.clone();
+        }
+
+        Ok(())
+    }
+
+    /// All export targets configured in this file, as (export flag name,
+    /// filename) pairs. Whether a command-line flag overrides one of them is
+    /// left to the caller.
+    pub fn export_targets(&self) -> Vec<(&'static str, &str)> {
+        let mut targets = Vec::new();
+        if let Some(filename) = &self.export_json {
+            targets.push(("export-json", filename.as_str()));
+        }
+        if let Some(filename) = &self.export_csv {
+            targets.push(("export-csv", filename.as_str()));
+        }
+        if let Some(filename) = &self.export_markdown {
+            targets.push(("export-markdown", filename.as_str()));
+        }
+        if let Some(filename) = &self.export_asciidoc {
+            targets.push(("export-asciidoc", filename.as_str()));
+        }
+        targets
+    }
+}
+
+#[test]
+fn test_apply_to_rejects_inverted_runs_range() {
+    let config = ConfigFile {
+        min_runs: Some(10),
+        max_runs: Some(5),
+        ..ConfigFile::default()
+    };
+    let mut options = HyperfineOptions::default();
+
+    assert!(matches!(
+        config.apply_to(&mut options),
+        Err(OptionsError::EmptyRunsRange)
+    ));
+}
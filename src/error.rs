@@ -0,0 +1,145 @@
+use std::cmp;
+use std::fmt;
+use std::num::{ParseFloatError, ParseIntError};
+
+use colored::*;
+
+/// Errors arising while turning CLI arguments (and config file values) into
+/// a valid `HyperfineOptions`.
+#[derive(Debug)]
+pub enum OptionsError<'a> {
+    NumericParsingError(&'a str, ParseIntError),
+    FloatParsingError(&'a str, ParseFloatError),
+    EmptyRunsRange,
+    UnexpectedCommandNameCount(usize, usize),
+    TooManyCommandNames(usize),
+
+    /// The value given for `option` is not one of `valid_values`.
+    InvalidOptionValue {
+        option: &'a str,
+        value: String,
+        valid_values: &'static [&'static str],
+    },
+}
+
+impl<'a> OptionsError<'a> {
+    /// A short, user-facing description of what went wrong.
+    fn description(&self) -> String {
+        match self {
+            OptionsError::NumericParsingError(param, e) => {
+                format!("Failed to parse number for the '--{}' option: {}", param, e)
+            }
+            OptionsError::FloatParsingError(param, e) => {
+                format!("Failed to parse number for the '--{}' option: {}", param, e)
+            }
+            OptionsError::EmptyRunsRange => {
+                "The minimum number of runs is larger than the maximum number of runs".into()
+            }
+            OptionsError::UnexpectedCommandNameCount(given, expected) => format!(
+                "The '--command-name' option has to be used either exactly once or exactly {} \
+                 times (number of benchmarked commands). Instead, it is used {} times.",
+                expected, given
+            ),
+            OptionsError::TooManyCommandNames(expected) => format!(
+                "Too many '--command-name' options: only up to {} names are allowed, one for \
+                 each benchmarked command.",
+                expected
+            ),
+            OptionsError::InvalidOptionValue { option, value, .. } => {
+                format!("Invalid value '{}' for the '--{}' option", value, option)
+            }
+        }
+    }
+
+    /// A remediation hint for this error, if one can be computed.
+    fn suggestion(&self) -> Option<String> {
+        match self {
+            OptionsError::InvalidOptionValue {
+                value,
+                valid_values,
+                ..
+            } => closest_match(value, valid_values)
+                .map(|candidate| format!("did you mean '{}'?", candidate)),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> fmt::Display for OptionsError<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.description())?;
+        if let Some(suggestion) = self.suggestion() {
+            write!(f, " ({})", suggestion)?;
+        }
+        Ok(())
+    }
+}
+
+/// Print a fatal error message to stderr and terminate.
+///
+/// This is the single place that formats a fatal error, so that every exit
+/// path - whether triggered by an `OptionsError` or a plain string message -
+/// gets the same "Error: ..." styling, honoring the color override that was
+/// configured for the current terminal (including the Windows virtual
+/// terminal).
+pub fn report(message: &str) -> ! {
+    eprintln!("{} {}", "Error:".red(), message);
+    std::process::exit(1);
+}
+
+/// Returns the entry in `candidates` that is closest to `value` by
+/// Levenshtein edit distance, provided it is close enough to plausibly be
+/// what the user meant to type.
+fn closest_match(value: &str, candidates: &[&'static str]) -> Option<&'static str> {
+    const MAX_SUGGESTION_DISTANCE: usize = 3;
+
+    candidates
+        .iter()
+        .map(|candidate| (*candidate, levenshtein_distance(value, candidate)))
+        .filter(|(_, distance)| *distance <= MAX_SUGGESTION_DISTANCE)
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(candidate, _)| candidate)
+}
+
+/// The classic dynamic-programming edit distance between two strings.
+fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        let mut prev = row[0];
+        row[0] = i;
+        for j in 1..=b.len() {
+            let tmp = row[j];
+            row[j] = if a[i - 1] == b[j - 1] {
+                prev
+            } else {
+                1 + cmp::min(prev, cmp::min(row[j], row[j - 1]))
+            };
+            prev = tmp;
+        }
+    }
+    row[b.len()]
+}
+
+#[test]
+fn test_closest_match_finds_typo() {
+    let candidates: &[&str] = &["full", "basic", "nocolor", "color", "none"];
+    assert_eq!(closest_match("colour", candidates), Some("color"));
+    assert_eq!(closest_match("ful", candidates), Some("full"));
+    assert_eq!(closest_match("xyzzy", candidates), None);
+}
+
+#[test]
+fn test_invalid_option_value_message_includes_suggestion() {
+    let err = OptionsError::InvalidOptionValue {
+        option: "style",
+        value: "colour".into(),
+        valid_values: &["full", "basic", "nocolor", "color", "none"],
+    };
+    assert_eq!(
+        err.to_string(),
+        "Invalid value 'colour' for the '--style' option (did you mean 'color'?)"
+    );
+}
@@ -2,15 +2,18 @@ use std::cmp;
 use std::collections::BTreeMap;
 use std::env;
 use std::io;
+use std::path::Path;
 
 use atty::Stream;
 use clap::ArgMatches;
 use colored::*;
 
 pub mod app;
+pub mod baseline;
 pub mod benchmark;
 pub mod benchmark_result;
 pub mod command;
+pub mod config;
 pub mod error;
 pub mod export;
 pub mod format;
@@ -28,9 +31,11 @@ pub mod units;
 pub mod warnings;
 
 use app::get_arg_matches;
+use baseline::{compare_against_baseline, load_baseline, print_comparisons};
 use benchmark::{mean_shell_spawning_time, run_benchmark};
 use benchmark_result::BenchmarkResult;
 use command::Command;
+use config::ConfigFile;
 use error::OptionsError;
 use export::{ExportManager, ExportType};
 use options::{CmdFailureAction, HyperfineOptions, OutputStyleOption, Shell};
@@ -41,8 +46,7 @@ use units::Unit;
 
 /// Print error message to stderr and terminate
 pub fn error(message: &str) -> ! {
-    eprintln!("{} {}", "Error:".red(), message);
-    std::process::exit(1);
+    error::report(message)
 }
 
 pub fn write_benchmark_comparison(results: &[BenchmarkResult]) {
@@ -122,14 +126,33 @@ fn run(
         write_benchmark_comparison(&timing_results);
     }
 
+    // Compare against a previous run, if requested
+    if let Some(baseline_file) = &options.baseline_file {
+        let baseline_results = load_baseline(Path::new(baseline_file)).unwrap_or_else(|e| error(&e));
+        let comparisons =
+            compare_against_baseline(&timing_results, &baseline_results, options.regression_threshold);
+        print_comparisons(&comparisons);
+
+        if options.fail_on_regression && comparisons.iter().any(|c| c.regressed) {
+            std::process::exit(1);
+        }
+    }
+
     Ok(())
 }
 
 fn main() {
     let matches = get_arg_matches(env::args_os());
-    let options = build_hyperfine_options(&matches);
+    let config = match matches.value_of("config") {
+        Some(path) => match ConfigFile::from_file(Path::new(path)) {
+            Ok(config) => Some(config),
+            Err(e) => error(&e),
+        },
+        None => None,
+    };
+    let options = build_hyperfine_options(&matches, config.as_ref());
     let commands = build_commands(&matches);
-    let export_manager = match build_export_manager(&matches) {
+    let export_manager = match build_export_manager(&matches, config.as_ref()) {
         Ok(export_manager) => export_manager,
         Err(ref e) => error(&e.to_string()),
     };
@@ -145,15 +168,22 @@ fn main() {
     }
 }
 
-/// Build the HyperfineOptions that correspond to the given ArgMatches
+/// Build the HyperfineOptions that correspond to the given ArgMatches. Any
+/// values present in `config` are applied first, as defaults that the CLI
+/// flags in `matches` are then free to override.
 fn build_hyperfine_options<'a>(
     matches: &ArgMatches<'a>,
+    config: Option<&ConfigFile>,
 ) -> Result<HyperfineOptions, OptionsError<'a>> {
     // Enabled ANSI colors on Windows 10
     #[cfg(windows)]
     colored::control::set_virtual_terminal(true).unwrap();
 
     let mut options = HyperfineOptions::default();
+    if let Some(config) = config {
+        config.apply_to(&mut options)?;
+    }
+
     let param_to_u64 = |param| {
         matches
             .value_of(param)
@@ -193,23 +223,42 @@ fn build_hyperfine_options<'a>(
         (None, None) => {}
     };
 
-    options.setup_command = matches.value_of("setup").map(String::from);
+    options.setup_command = matches
+        .value_of("setup")
+        .map(String::from)
+        .or(options.setup_command);
 
     options.preparation_command = matches
         .values_of("prepare")
-        .map(|values| values.map(String::from).collect::<Vec<String>>());
+        .map(|values| values.map(String::from).collect::<Vec<String>>())
+        .or(options.preparation_command);
 
-    options.cleanup_command = matches.value_of("cleanup").map(String::from);
+    options.cleanup_command = matches
+        .value_of("cleanup")
+        .map(String::from)
+        .or(options.cleanup_command);
 
     options.show_output = matches.is_present("show-output");
 
-    options.output_style = match matches.value_of("style") {
+    const VALID_STYLES: &[&str] = &["full", "basic", "nocolor", "color", "none"];
+
+    let style = matches
+        .value_of("style")
+        .or_else(|| config.and_then(|c| c.style.as_deref()));
+    options.output_style = match style {
         Some("full") => OutputStyleOption::Full,
         Some("basic") => OutputStyleOption::Basic,
         Some("nocolor") => OutputStyleOption::NoColor,
         Some("color") => OutputStyleOption::Color,
         Some("none") => OutputStyleOption::Disabled,
-        _ => {
+        Some(other) => {
+            return Err(OptionsError::InvalidOptionValue {
+                option: "style",
+                value: other.to_string(),
+                valid_values: VALID_STYLES,
+            })
+        }
+        None => {
             if !options.show_output && atty::is(Stream::Stdout) {
                 OutputStyleOption::Full
             } else {
@@ -226,7 +275,10 @@ fn build_hyperfine_options<'a>(
         OutputStyleOption::Disabled => {}
     };
 
-    if let Some(shell) = matches.value_of("shell") {
+    let shell = matches
+        .value_of("shell")
+        .or_else(|| config.and_then(|c| c.shell.as_deref()));
+    if let Some(shell) = shell {
         options.shell = Shell::parse(shell)?;
     }
 
@@ -234,22 +286,65 @@ fn build_hyperfine_options<'a>(
         options.failure_action = CmdFailureAction::Ignore;
     }
 
-    options.time_unit = match matches.value_of("time-unit") {
+    const VALID_TIME_UNITS: &[&str] = &[
+        "nanosecond",
+        "microsecond",
+        "millisecond",
+        "second",
+        "minute",
+    ];
+
+    let time_unit = matches
+        .value_of("time-unit")
+        .or_else(|| config.and_then(|c| c.time_unit.as_deref()));
+    options.time_unit = match time_unit {
+        Some("nanosecond") => Some(Unit::NanoSecond),
+        Some("microsecond") => Some(Unit::MicroSecond),
         Some("millisecond") => Some(Unit::MilliSecond),
         Some("second") => Some(Unit::Second),
-        _ => None,
+        Some("minute") => Some(Unit::Minute),
+        Some(other) => {
+            return Err(OptionsError::InvalidOptionValue {
+                option: "time-unit",
+                value: other.to_string(),
+                valid_values: VALID_TIME_UNITS,
+            })
+        }
+        None => None,
     };
 
+    options.baseline_file = matches.value_of("baseline").map(String::from);
+    options.fail_on_regression = matches.is_present("fail-on-regression");
+    options.regression_threshold = matches
+        .value_of("regression-threshold")
+        .map(|v| {
+            v.parse::<f64>()
+                .map_err(|e| OptionsError::FloatParsingError("regression-threshold", e))
+        })
+        .transpose()?
+        .unwrap_or(options.regression_threshold);
+
     Ok(options)
 }
 
 /// Build the ExportManager that will export the results specified
-/// in the given ArgMatches
-fn build_export_manager(matches: &ArgMatches<'_>) -> io::Result<ExportManager> {
+/// in the given ArgMatches, falling back to the export targets from `config`
+/// for any flag that was not also given on the command line.
+fn build_export_manager(
+    matches: &ArgMatches<'_>,
+    config: Option<&ConfigFile>,
+) -> io::Result<ExportManager> {
     let mut export_manager = ExportManager::default();
     {
+        let config_targets = config.map(ConfigFile::export_targets).unwrap_or_default();
         let mut add_exporter = |flag, exporttype| -> io::Result<()> {
-            if let Some(filename) = matches.value_of(flag) {
+            let filename = matches.value_of(flag).or_else(|| {
+                config_targets
+                    .iter()
+                    .find(|(f, _)| *f == flag)
+                    .map(|(_, filename)| *filename)
+            });
+            if let Some(filename) = filename {
                 export_manager.add_exporter(exporttype, filename)?;
             }
             Ok(())
@@ -0,0 +1,96 @@
+/// A unit used to display benchmark times, from smallest to largest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    NanoSecond,
+    MicroSecond,
+    MilliSecond,
+    Second,
+    Minute,
+}
+
+impl Unit {
+    /// How many of this unit make up one second.
+    fn per_second(self) -> f64 {
+        match self {
+            Unit::NanoSecond => 1e9,
+            Unit::MicroSecond => 1e6,
+            Unit::MilliSecond => 1e3,
+            Unit::Second => 1.0,
+            Unit::Minute => 1.0 / 60.0,
+        }
+    }
+
+    /// The short, user-facing label for this unit (e.g. "ms", "µs").
+    pub fn short_name(self) -> &'static str {
+        match self {
+            Unit::NanoSecond => "ns",
+            Unit::MicroSecond => "µs",
+            Unit::MilliSecond => "ms",
+            Unit::Second => "s",
+            Unit::Minute => "min",
+        }
+    }
+
+    /// Convert a duration given in seconds into this unit.
+    pub fn from_seconds(self, seconds: f64) -> f64 {
+        seconds * self.per_second()
+    }
+}
+
+/// All units, ordered from smallest to largest, consulted by [`auto_unit`]
+/// when picking the most readable one for a given mean time.
+const ALL_UNITS: [Unit; 5] = [
+    Unit::NanoSecond,
+    Unit::MicroSecond,
+    Unit::MilliSecond,
+    Unit::Second,
+    Unit::Minute,
+];
+
+/// Pick the unit that keeps `mean_seconds` in a human-readable `1..1000`
+/// range, so that very fast commands are shown in µs/ns and long-running
+/// ones in minutes, without the user having to pass `--time-unit` by hand.
+/// Falls back to the smallest or largest unit if no candidate lands in
+/// range (e.g. a mean time of exactly zero).
+///
+/// `Second`'s window is capped at 60 so that multi-minute means (which would
+/// otherwise still read as a "readable" number of seconds) round up to
+/// `Minute` instead.
+pub fn auto_unit(mean_seconds: f64) -> Unit {
+    if mean_seconds >= 60.0 {
+        return Unit::Minute;
+    }
+
+    ALL_UNITS
+        .iter()
+        .copied()
+        .find(|unit| (1.0..1000.0).contains(&unit.from_seconds(mean_seconds)))
+        .unwrap_or(Unit::NanoSecond)
+}
+
+/// Format a duration given in seconds using whichever unit `auto_unit`
+/// deems most readable for it (e.g. `"512.0 µs"`, `"3.2 min"`).
+pub fn format_duration(seconds: f64) -> String {
+    let unit = auto_unit(seconds);
+    format!("{:.1} {}", unit.from_seconds(seconds), unit.short_name())
+}
+
+#[test]
+fn test_auto_unit_picks_readable_range() {
+    assert_eq!(auto_unit(0.0000005), Unit::NanoSecond); // 500 ns
+    assert_eq!(auto_unit(0.000005), Unit::MicroSecond); // 5 µs
+    assert_eq!(auto_unit(0.005), Unit::MilliSecond); // 5 ms
+    assert_eq!(auto_unit(5.0), Unit::Second); // 5 s
+    assert_eq!(auto_unit(300.0), Unit::Minute); // 5 min
+}
+
+#[test]
+fn test_auto_unit_falls_back_for_zero() {
+    assert_eq!(auto_unit(0.0), Unit::NanoSecond);
+}
+
+#[test]
+fn test_format_duration() {
+    assert_eq!(format_duration(0.0005), "500.0 µs");
+    assert_eq!(format_duration(190.0), "3.2 min");
+}